@@ -0,0 +1,239 @@
+use crate::Map;
+use std::collections::VecDeque;
+
+impl<D> Map<D> {
+    /// Flood-fills the floor cells (`get(y, x) == false`) into disjoint
+    /// regions using 4-neighbour connectivity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(5, 5);
+    /// let regions = map.connected_regions();
+    ///
+    /// // an empty map has a single region covering every cell
+    /// assert_eq!(1, regions.len());
+    /// assert_eq!(25, regions[0].len());
+    /// ```
+    pub fn connected_regions(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut visited = vec![false; self.height * self.width];
+        let mut regions = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(y, x) || visited[y * self.width + x] {
+                    continue;
+                }
+
+                regions.push(self.flood_fill(y, x, &mut visited));
+            }
+        }
+
+        regions
+    }
+
+    fn flood_fill(&self, y: usize, x: usize, visited: &mut [bool]) -> Vec<(usize, usize)> {
+        let mut region = Vec::new();
+        let mut queue = VecDeque::new();
+
+        visited[y * self.width + x] = true;
+        queue.push_back((y, x));
+
+        while let Some((cy, cx)) = queue.pop_front() {
+            region.push((cy, cx));
+
+            for (ny, nx) in self.neighbours(cy, cx) {
+                let idx = ny * self.width + nx;
+                if self.get(ny, nx) || visited[idx] {
+                    continue;
+                }
+
+                visited[idx] = true;
+                queue.push_back((ny, nx));
+            }
+        }
+
+        region
+    }
+
+    pub(crate) fn neighbours(&self, y: usize, x: usize) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+
+        if y > 0 {
+            result.push((y - 1, x));
+        }
+        if y + 1 < self.height {
+            result.push((y + 1, x));
+        }
+        if x > 0 {
+            result.push((y, x - 1));
+        }
+        if x + 1 < self.width {
+            result.push((y, x + 1));
+        }
+
+        result
+    }
+
+    /// Makes the whole cave traversable: keeps the largest floor region and
+    /// carves an L-shaped corridor from every other region's nearest-to-centroid
+    /// cell to the nearest cell of the kept region, measured by Manhattan
+    /// distance. The centroid of a concave region is not necessarily a member
+    /// of that region, so it is first snapped to the closest actual cell.
+    ///
+    /// Regions smaller than `min_region_size` are filled back to wall instead
+    /// of being connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let mut map = Map::gen_cave_seed(40, 40, String::from("0"));
+    /// map.ensure_connected(0);
+    ///
+    /// assert_eq!(1, map.connected_regions().len());
+    /// ```
+    pub fn ensure_connected(&mut self, min_region_size: usize) {
+        let mut regions = self.connected_regions();
+        if regions.len() <= 1 {
+            return;
+        }
+
+        regions.sort_by_key(|region| region.len());
+        let kept = regions.pop().expect("checked regions.len() > 1 above");
+
+        for region in regions {
+            if region.len() < min_region_size {
+                for &(y, x) in &region {
+                    self.set(y, x, true);
+                }
+                continue;
+            }
+
+            let source = nearest_by_manhattan(centroid(&region), &region);
+            let target = nearest_by_manhattan(source, &kept);
+            self.carve_l_corridor(source, target);
+        }
+    }
+
+    fn carve_l_corridor(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (fy, fx) = from;
+        let (ty, tx) = to;
+
+        let (lo, hi) = (fx.min(tx), fx.max(tx));
+        for x in lo..=hi {
+            self.set(fy, x, false);
+        }
+
+        let (lo, hi) = (fy.min(ty), fy.max(ty));
+        for y in lo..=hi {
+            self.set(y, tx, false);
+        }
+    }
+}
+
+fn centroid(region: &[(usize, usize)]) -> (usize, usize) {
+    let (sum_y, sum_x) = region
+        .iter()
+        .fold((0usize, 0usize), |(sy, sx), &(y, x)| (sy + y, sx + x));
+
+    (sum_y / region.len(), sum_x / region.len())
+}
+
+fn nearest_by_manhattan(from: (usize, usize), candidates: &[(usize, usize)]) -> (usize, usize) {
+    *candidates
+        .iter()
+        .min_by_key(|&&(y, x)| manhattan_distance(from, (y, x)))
+        .expect("regions are never empty")
+}
+
+fn manhattan_distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_region_on_empty_map() {
+        let map: Map = Map::new(5, 5);
+        let regions = map.connected_regions();
+
+        assert_eq!(1, regions.len());
+        assert_eq!(25, regions[0].len());
+    }
+
+    #[test]
+    fn splits_into_separate_regions() {
+        let mut map: Map = Map::new(3, 3);
+        for x in 0..3 {
+            map.set(1, x, true);
+        }
+
+        let regions = map.connected_regions();
+
+        assert_eq!(2, regions.len());
+        assert_eq!(3, regions[0].len());
+        assert_eq!(3, regions[1].len());
+    }
+
+    #[test]
+    fn ensure_connected_merges_all_regions() {
+        let mut map: Map = Map::new(3, 3);
+        for x in 0..3 {
+            map.set(1, x, true);
+        }
+
+        map.ensure_connected(0);
+
+        assert_eq!(1, map.connected_regions().len());
+    }
+
+    #[test]
+    fn ensure_connected_reaches_concave_regions() {
+        // A 17-cell "L"-shaped region whose centroid, (2, 2), is a wall cell
+        // outside the region itself, plus a separate 20-cell region to keep.
+        let mut map: Map = Map::new(14, 14);
+        for y in 0..14 {
+            for x in 0..14 {
+                map.set(y, x, true);
+            }
+        }
+
+        for x in 0..9 {
+            map.set(0, x, false);
+        }
+        for y in 1..9 {
+            map.set(y, 0, false);
+        }
+
+        for y in 0..5 {
+            for x in 10..14 {
+                map.set(y, x, false);
+            }
+        }
+
+        assert_eq!(2, map.connected_regions().len());
+
+        map.ensure_connected(0);
+
+        assert_eq!(1, map.connected_regions().len());
+    }
+
+    #[test]
+    fn ensure_connected_discards_tiny_regions() {
+        let mut map: Map = Map::new(5, 5);
+        map.set(3, 4, true);
+        map.set(4, 3, true);
+
+        map.ensure_connected(2);
+
+        let regions = map.connected_regions();
+        assert_eq!(1, regions.len());
+        assert!(!regions[0].contains(&(4, 4)));
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{Map, NoData};
+use rand::prelude::*;
+
+const MIN_KEEP_WALL: u32 = 4;
+const MIN_NEW_WALL: u32 = 5;
+
+/// A single step in a [`MapBuilder`](crate::MapBuilder) pipeline.
+///
+/// A filter reads an existing `Map` and produces a new one, so a pipeline is
+/// just an ordered list of filters applied in sequence.
+pub trait MapFilter<D = NoData> {
+    fn apply(&self, map: &Map<D>, rng: &mut dyn RngCore) -> Map<D>;
+}
+
+/// Fills every cell with a wall independently at the given probability.
+///
+/// # Examples
+///
+/// ```
+/// use map_generator::{Map, MapBuilder, RandomNoise};
+/// use rand_pcg::Pcg64;
+/// use rand::SeedableRng;
+///
+/// let mut rng = Pcg64::seed_from_u64(0);
+/// let map: Map = MapBuilder::new(Map::new(10, 10))
+///     .add_filter(RandomNoise { probability: 0.45 })
+///     .build(&mut rng);
+/// ```
+pub struct RandomNoise {
+    pub probability: f64,
+}
+
+impl<D: Clone + Default> MapFilter<D> for RandomNoise {
+    fn apply(&self, map: &Map<D>, rng: &mut dyn RngCore) -> Map<D> {
+        let mut new_map = new_map_like(map);
+
+        for i in 0..map.height {
+            for j in 0..map.width {
+                new_map.set(i, j, rng.gen_bool(self.probability));
+            }
+        }
+
+        new_map
+    }
+}
+
+/// A configurable cellular-automata smoothing rule: a wall survives if its
+/// radius-`radius` neighbourhood has at least `survival_limit` walls, and a
+/// floor becomes a wall if that neighbourhood has at least `birth_limit`
+/// walls. `fill_probability` is the noise density this rule is tuned for,
+/// for callers wiring up the matching [`RandomNoise`] step.
+///
+/// If `isolated_pocket_radius` is set, a cell is also forced to a wall when
+/// its neighbourhood out to that (larger) radius has no walls at all,
+/// breaking up wide open areas that the birth/survival check alone would
+/// leave as floor.
+///
+/// # Examples
+///
+/// ```
+/// use map_generator::{Map, MapBuilder, RandomNoise, CaRule};
+/// use rand_pcg::Pcg64;
+/// use rand::SeedableRng;
+///
+/// let mut rng = Pcg64::seed_from_u64(0);
+/// let rule = CaRule::classic();
+/// let map: Map = MapBuilder::new(Map::new(10, 10))
+///     .add_filter(RandomNoise { probability: rule.fill_probability })
+///     .add_filter(rule)
+///     .build(&mut rng);
+/// ```
+pub struct CaRule {
+    pub birth_limit: u32,
+    pub survival_limit: u32,
+    pub radius: usize,
+    pub iterations: usize,
+    pub fill_probability: f64,
+    pub isolated_pocket_radius: Option<usize>,
+}
+
+impl CaRule {
+    /// The rule `gen_cave_seed`/`gen_cave_no_seed` build on: a radius-1
+    /// neighbourhood with the classic birth limit of 5 and survival limit of
+    /// 4, plus the radius-2 isolated-pocket check the original implementation
+    /// used to drop a wall into any cell with no wall within two cells.
+    pub fn classic() -> CaRule {
+        CaRule {
+            birth_limit: MIN_NEW_WALL,
+            survival_limit: MIN_KEEP_WALL,
+            radius: 1,
+            iterations: 5,
+            fill_probability: 0.45,
+            isolated_pocket_radius: Some(2),
+        }
+    }
+
+    fn next_generation<D: Clone + Default>(&self, map: &Map<D>) -> Map<D> {
+        let mut new_map = new_map_like(map);
+
+        for i in 0..map.height {
+            for j in 0..map.width {
+                new_map.set(i, j, self.calculate_new_cell(map, i, j));
+            }
+        }
+
+        new_map
+    }
+
+    fn calculate_new_cell<D>(&self, map: &Map<D>, y: usize, x: usize) -> bool {
+        let is_isolated_pocket = self
+            .isolated_pocket_radius
+            .is_some_and(|radius| map.count_walls_within(y, x, radius) == 0);
+
+        if is_isolated_pocket {
+            return true;
+        }
+
+        let walls = map.count_walls_within(y, x, self.radius);
+
+        if map.get(y, x) {
+            walls >= self.survival_limit
+        } else {
+            walls >= self.birth_limit
+        }
+    }
+}
+
+impl<D: Clone + Default> MapFilter<D> for CaRule {
+    fn apply(&self, map: &Map<D>, _rng: &mut dyn RngCore) -> Map<D> {
+        let mut current = self.next_generation(map);
+        for _ in 1..self.iterations {
+            current = self.next_generation(&current);
+        }
+
+        current
+    }
+}
+
+/// Creates an empty map with the same dimensions and data as `map`, ready for
+/// a filter to populate its own grid of walls and floors.
+fn new_map_like<D: Clone + Default>(map: &Map<D>) -> Map<D> {
+    let mut new_map = Map::new(map.height, map.width);
+    *new_map.data_mut() = map.data().clone();
+
+    new_map
+}
+
+/// One more radius-1 pass with the classic limits and no isolated-pocket
+/// check: walls with fewer than four wall neighbours become floor, and floors
+/// with five or more become walls. Intended as a final pass to tidy up stray single-cell walls left behind
+/// by smoothing.
+pub struct Cleanup;
+
+impl<D: Clone + Default> MapFilter<D> for Cleanup {
+    fn apply(&self, map: &Map<D>, _rng: &mut dyn RngCore) -> Map<D> {
+        let mut new_map = new_map_like(map);
+
+        for i in 0..map.height {
+            for j in 0..map.width {
+                let walls = map.count_walls_within(i, j, 1);
+                let keep_wall = walls == MIN_KEEP_WALL && map.get(i, j);
+                new_map.set(i, j, walls >= MIN_NEW_WALL || keep_wall);
+            }
+        }
+
+        new_map
+    }
+}
+
+/// Builds a `Map` by running a starting map through an ordered pipeline of
+/// [`MapFilter`]s.
+///
+/// # Examples
+///
+/// ```
+/// use map_generator::{Map, MapBuilder, RandomNoise, CaRule, Cleanup};
+/// use rand_pcg::Pcg64;
+/// use rand::SeedableRng;
+///
+/// let mut rng = Pcg64::seed_from_u64(0);
+/// let map: Map = MapBuilder::new(Map::new(10, 10))
+///     .add_filter(RandomNoise { probability: 0.45 })
+///     .add_filter(CaRule { iterations: 3, ..CaRule::classic() })
+///     .add_filter(Cleanup)
+///     .add_filter(RandomNoise { probability: 0.1 })
+///     .build(&mut rng);
+/// ```
+pub struct MapBuilder<D = NoData> {
+    map: Map<D>,
+    filters: Vec<Box<dyn MapFilter<D>>>,
+}
+
+impl<D: Clone + Default> MapBuilder<D> {
+    pub fn new(map: Map<D>) -> MapBuilder<D> {
+        MapBuilder {
+            map,
+            filters: Vec::new(),
+        }
+    }
+
+    pub fn add_filter(mut self, filter: impl MapFilter<D> + 'static) -> MapBuilder<D> {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub fn build<T: RngCore>(self, rng: &mut T) -> Map<D> {
+        let mut map = self.map;
+        for filter in &self.filters {
+            map = filter.apply(&map, rng);
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn count_walls_within_matches_moore_neighbourhood() {
+        let map: Map = Map::new(1, 1);
+        assert_eq!(8, map.count_walls_within(0, 0, 1));
+
+        let mut map: Map = Map::new(3, 3);
+        assert_eq!(0, map.count_walls_within(1, 1, 1));
+
+        map.set(0, 0, true);
+        assert_eq!(1, map.count_walls_within(1, 1, 1));
+
+        map.set(2, 2, true);
+        assert_eq!(2, map.count_walls_within(1, 1, 1));
+    }
+
+    #[test]
+    fn classic_rule_matches_original_thresholds() {
+        let rule = CaRule::classic();
+
+        let map: Map = Map::new(1, 1);
+        assert!(rule.calculate_new_cell(&map, 0, 0));
+
+        let mut map: Map = Map::new(3, 3);
+        assert!(!rule.calculate_new_cell(&map, 1, 1));
+
+        map.set(0, 0, true);
+        map.set(0, 1, true);
+        map.set(0, 2, true);
+        map.set(1, 2, true);
+        assert!(!rule.calculate_new_cell(&map, 1, 1));
+
+        map.set(1, 1, true);
+        assert!(rule.calculate_new_cell(&map, 1, 1));
+    }
+
+    #[test]
+    fn classic_rule_fills_isolated_pockets() {
+        let rule = CaRule::classic();
+
+        // A floor cell with no walls anywhere out to radius 2 has no
+        // neighbouring walls within its radius-1 birth/survival check either,
+        // so without the isolated-pocket term it would stay floor.
+        let map: Map = Map::new(5, 5);
+
+        assert!(rule.calculate_new_cell(&map, 2, 2));
+    }
+
+    #[test]
+    fn larger_radius_considers_a_wider_neighbourhood() {
+        let rule = CaRule {
+            birth_limit: 1,
+            survival_limit: 1,
+            radius: 2,
+            iterations: 1,
+            fill_probability: 0.0,
+            isolated_pocket_radius: None,
+        };
+
+        let mut map: Map = Map::new(5, 5);
+        map.set(0, 0, true);
+
+        assert!(rule.calculate_new_cell(&map, 2, 2));
+    }
+
+    #[test]
+    fn custom_pipeline_runs_filters_in_order() {
+        let mut rng = Pcg64::seed_from_u64(0);
+        let map: Map = MapBuilder::new(Map::new(10, 10))
+            .add_filter(RandomNoise { probability: 0.45 })
+            .add_filter(CaRule::classic())
+            .add_filter(Cleanup)
+            .build(&mut rng);
+
+        let map_string = format!("{}", map);
+
+        let expected_map_string = String::from(
+            "\
+############
+############
+############
+###...######
+##......####
+##......####
+##......####
+##.....#####
+##....######
+###..#######
+############
+############",
+        );
+
+        assert_eq!(expected_map_string, map_string);
+    }
+
+    #[test]
+    fn custom_pipeline_preserves_attached_data() {
+        let mut rng = Pcg64::seed_from_u64(0);
+        let mut start: Map<u32> = Map::new(5, 5);
+        *start.data_mut() = 7;
+
+        let map = MapBuilder::new(start)
+            .add_filter(RandomNoise { probability: 0.45 })
+            .add_filter(CaRule {
+                iterations: 1,
+                ..CaRule::classic()
+            })
+            .build(&mut rng);
+
+        assert_eq!(&7, map.data());
+    }
+}
@@ -4,39 +4,32 @@ use std::collections::hash_map::DefaultHasher;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
+mod connectivity;
+mod filters;
+mod metrics;
+mod pathfinding;
+pub use filters::{CaRule, Cleanup, MapBuilder, MapFilter, RandomNoise};
+pub use metrics::MapMetrics;
+
 const WALL: char = '#';
 const FLOOR: char = '.';
-const INIT_PROBABILITY: f64 = 0.45;
-const MIN_KEEP_WALL: u8 = 4;
-const MIN_NEW_WALL: u8 = 5;
+const CA_ITERATIONS: usize = 5;
+
+/// The payload type used by [`Map`] when a caller has no per-cell data to
+/// attach, preserving the original wall/floor-only behavior.
+///
+/// Note that a map carries one value of its data type, not one per cell.
+#[derive(Clone, Default)]
+pub struct NoData;
 
-pub struct Map {
+pub struct Map<D = NoData> {
     map: Vec<bool>,
     pub height: usize,
     pub width: usize,
+    data: D,
 }
 
-impl Map {
-    /// Create empty map
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use map_generator::Map;
-    ///
-    /// let map = Map::new(100,50);
-    ///
-    /// assert_eq!(100, map.height);
-    /// assert_eq!(50, map.width);
-    /// ```
-    pub fn new(height: usize, width: usize) -> Map {
-        Map {
-            map: vec![false; width * height],
-            width,
-            height,
-        }
-    }
-
+impl<D> Map<D> {
     /// Check if a wall is present at a given position.
     ///
     /// # Examples
@@ -68,7 +61,7 @@ impl Map {
     /// ```
     /// use map_generator::Map;
     ///
-    /// let mut map = Map::new(10,10);
+    /// let mut map: Map = Map::new(10,10);
     /// assert!(!map.get(0,0));
     ///
     /// map.set(0,0,true);
@@ -80,132 +73,77 @@ impl Map {
         }
     }
 
-    fn next_cellular_automata(&self) -> Map {
-        let mut new_map = Map::new(self.height, self.width);
-
-        for i in 0..self.height {
-            for j in 0..self.width {
-                new_map.set(i, j, self.calculate_new_cell(i, j));
-            }
-        }
-
-        new_map
+    /// The data attached to this map: a single value of `D` for the whole
+    /// map, not one per cell.
+    pub fn data(&self) -> &D {
+        &self.data
     }
 
-    fn calculate_new_cell(&self, y: usize, x: usize) -> bool {
-        let num_neighbours = self.count_neighbours(y, x);
-
-        if num_neighbours >= MIN_NEW_WALL || self.empty_space(y, x) {
-            return true;
-        }
-        if num_neighbours == MIN_KEEP_WALL && self.get(y, x) {
-            return true;
-        }
-
-        false
+    /// A mutable handle to the data attached to this map, for a
+    /// filter to record spawn points, region ids, or other annotations.
+    pub fn data_mut(&mut self) -> &mut D {
+        &mut self.data
     }
 
-    fn empty_space(&self, y: usize, x: usize) -> bool {
-        if self.count_far_neighbours(y, x) == 0 {
-            return true;
-        }
-        false
-    }
-
-    fn count_far_neighbours(&self, y: usize, x: usize) -> u8 {
-        let mut total = self.count_neighbours(y, x);
-
-        if x <= 1 || y <= 1 || self.get(y - 2, x - 2) {
-            total += 1;
-        }
-        if x == 0 || y <= 1 || self.get(y - 2, x - 1) {
-            total += 1;
-        }
-        if y <= 1 || self.get(y - 2, x) {
-            total += 1;
-        }
-        if y <= 1 || self.get(y - 2, x + 1) {
-            total += 1;
-        }
-        if y <= 1 || self.get(y - 2, x + 2) {
-            total += 1;
-        }
-        if y == 0 || self.get(y - 1, x + 2) {
-            total += 1;
-        }
-        if self.get(y, x + 2) {
-            total += 1;
-        }
-        if self.get(y + 1, x + 2) {
-            total += 1;
-        }
-        if self.get(y + 2, x + 2) {
-            total += 1;
-        }
-        if self.get(y + 2, x + 1) {
-            total += 1;
-        }
-        if self.get(y + 2, x) {
-            total += 1;
-        }
-        if x == 0 || self.get(y + 2, x - 1) {
-            total += 1;
-        }
-        if x <= 1 || self.get(y + 2, x - 2) {
-            total += 1;
-        }
-        if x <= 1 || self.get(y + 1, x - 2) {
-            total += 1;
-        }
-        if x <= 1 || self.get(y, x - 2) {
-            total += 1;
-        }
-        if x <= 1 || y == 0 || self.get(y - 1, x - 2) {
-            total += 1;
-        }
-
-        total
-    }
-
-    fn count_neighbours(&self, y: usize, x: usize) -> u8 {
-        let mut neighbours = 0;
+    /// Counts the walls in the square neighbourhood of `(y, x)` out to the
+    /// given Chebyshev `radius`, not including `(y, x)` itself. Cells outside
+    /// the map are treated as walls, as [`get`](Map::get) already does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let mut map: Map = Map::new(3, 3);
+    /// map.set(0, 0, true);
+    ///
+    /// assert_eq!(1, map.count_walls_within(1, 1, 1));
+    /// ```
+    pub fn count_walls_within(&self, y: usize, x: usize, radius: usize) -> u32 {
+        let radius = radius as isize;
+        let (y, x) = (y as isize, x as isize);
+        let mut count = 0;
+
+        for ny in y - radius..=y + radius {
+            for nx in x - radius..=x + radius {
+                if (ny, nx) == (y, x) {
+                    continue;
+                }
 
-        if x == 0 || y == 0 || self.get(y - 1, x - 1) {
-            neighbours += 1;
-        }
-        if y == 0 || self.get(y - 1, x) {
-            neighbours += 1;
-        }
-        if y == 0 || self.get(y - 1, x + 1) {
-            neighbours += 1;
-        }
-        if self.get(y, x + 1) {
-            neighbours += 1;
-        }
-        if self.get(y + 1, x + 1) {
-            neighbours += 1;
-        }
-        if self.get(y + 1, x) {
-            neighbours += 1;
-        }
-        if x == 0 || self.get(y + 1, x - 1) {
-            neighbours += 1;
-        }
-        if x == 0 || self.get(y, x - 1) {
-            neighbours += 1;
+                if ny < 0 || nx < 0 || self.get(ny as usize, nx as usize) {
+                    count += 1;
+                }
+            }
         }
 
-        neighbours
+        count
     }
+}
 
-    fn fill_random<T: Rng>(&mut self, rng: &mut T) {
-        for i in 0..self.height {
-            for j in 0..self.width {
-                self.set(i, j, rng.gen_bool(INIT_PROBABILITY));
-            }
+impl<D: Default> Map<D> {
+    /// Create empty map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(100,50);
+    ///
+    /// assert_eq!(100, map.height);
+    /// assert_eq!(50, map.width);
+    /// ```
+    pub fn new(height: usize, width: usize) -> Map<D> {
+        Map {
+            map: vec![false; width * height],
+            width,
+            height,
+            data: D::default(),
         }
     }
+}
 
+impl Map<NoData> {
     /// Creates a cave based on a given seed
     ///
     /// # Examples
@@ -217,7 +155,7 @@ impl Map {
     /// assert!(map.get(0,0));
     /// assert!(!map.get(3,2));
     /// ```
-    pub fn gen_cave_seed(y: usize, x: usize, seed: String) -> Map {
+    pub fn gen_cave_seed(y: usize, x: usize, seed: String) -> Map<NoData> {
         let mut s = DefaultHasher::new();
 
         let seed: u64 = match seed.trim().parse() {
@@ -241,23 +179,31 @@ impl Map {
     ///
     /// let map = Map::gen_cave_no_seed(10,10);
     /// ```
-    pub fn gen_cave_no_seed(y: usize, x: usize) -> Map {
+    pub fn gen_cave_no_seed(y: usize, x: usize) -> Map<NoData> {
         let mut rng = rand::thread_rng();
         Map::gen_cave(y, x, &mut rng)
     }
 
-    fn gen_cave<T: Rng>(y: usize, x: usize, rng: &mut T) -> Map {
-        let mut map = Map::new(y, x);
-        map.fill_random(rng);
-        for _ in 0..5 {
-            map = map.next_cellular_automata();
-        }
-
-        map
+    /// Runs the default cave-generation pipeline: random noise followed by
+    /// five passes of cellular-automata smoothing. Build a [`MapBuilder`]
+    /// directly instead if you want a different pipeline, e.g. one that adds
+    /// a [`Cleanup`] pass.
+    fn gen_cave<T: Rng>(y: usize, x: usize, rng: &mut T) -> Map<NoData> {
+        let rule = CaRule::classic();
+
+        MapBuilder::new(Map::new(y, x))
+            .add_filter(RandomNoise {
+                probability: rule.fill_probability,
+            })
+            .add_filter(CaRule {
+                iterations: CA_ITERATIONS,
+                ..rule
+            })
+            .build(rng)
     }
 }
 
-impl fmt::Display for Map {
+impl<D> fmt::Display for Map<D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         for _ in 0..self.width + 2 {
             write!(f, "{}", WALL)?;
@@ -287,7 +233,7 @@ mod tests {
 
     #[test]
     fn create_map() {
-        let map = Map::new(100, 50);
+        let map: Map = Map::new(100, 50);
 
         assert_eq!(100, map.height);
         assert_eq!(50, map.width);
@@ -295,7 +241,7 @@ mod tests {
 
     #[test]
     fn create_empty_map() {
-        let map = Map::new(0, 0);
+        let map: Map = Map::new(0, 0);
 
         assert_eq!(0, map.height);
         assert_eq!(0, map.width);
@@ -303,21 +249,21 @@ mod tests {
 
     #[test]
     fn get_value_in_bounds() {
-        let map = Map::new(10, 10);
+        let map: Map = Map::new(10, 10);
 
         assert!(!map.get(0, 0));
     }
 
     #[test]
     fn get_value_out_of_bounds() {
-        let map = Map::new(10, 10);
+        let map: Map = Map::new(10, 10);
 
         assert!(map.get(100, 100));
     }
 
     #[test]
     fn set_value() {
-        let mut map = Map::new(10, 10);
+        let mut map: Map = Map::new(10, 10);
 
         map.set(0, 0, true);
 
@@ -329,70 +275,12 @@ mod tests {
     }
 
     #[test]
-    fn correct_neighbour_count() {
-        let map = Map::new(1, 1);
-        let num_neighbours = map.count_neighbours(0, 0);
-        assert_eq!(8, num_neighbours);
-
-        let mut map = Map::new(3, 3);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(0, num_neighbours);
-
-        map.set(0, 0, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(1, num_neighbours);
-
-        map.set(0, 1, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(2, num_neighbours);
-
-        map.set(0, 2, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(3, num_neighbours);
-
-        map.set(1, 2, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(4, num_neighbours);
-
-        map.set(2, 2, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(5, num_neighbours);
-
-        map.set(2, 1, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(6, num_neighbours);
+    fn data_defaults_and_can_be_mutated() {
+        let mut map: Map<Vec<(usize, usize)>> = Map::new(5, 5);
+        assert!(map.data().is_empty());
 
-        map.set(2, 0, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(7, num_neighbours);
-
-        map.set(1, 0, true);
-        let num_neighbours = map.count_neighbours(1, 1);
-        assert_eq!(8, num_neighbours);
-    }
-
-    #[test]
-    fn correct_new_char() {
-        let map = Map::new(1, 1);
-        assert!(map.calculate_new_cell(0, 0));
-
-        let mut map = Map::new(3, 3);
-        assert!(!map.calculate_new_cell(1, 1));
-
-        map.set(0, 0, true);
-        assert!(!map.calculate_new_cell(1, 1));
-
-        map.set(0, 1, true);
-        map.set(0, 2, true);
-        map.set(1, 2, true);
-        assert!(!map.calculate_new_cell(1, 1));
-
-        map.set(1, 1, true);
-        assert!(map.calculate_new_cell(1, 1));
-
-        map.set(1, 1, false);
-        map.set(2, 2, true);
-        assert!(map.calculate_new_cell(1, 1));
+        map.data_mut().push((1, 2));
+        assert_eq!(&vec![(1, 2)], map.data());
     }
 
     #[test]
@@ -421,9 +309,54 @@ mod tests {
         assert_eq!(expected_map_string, map_string);
     }
 
+    #[test]
+    fn generate_map_fills_isolated_pockets() {
+        // Seeds 2 and 3 each produce a floor cell with no walls anywhere in
+        // its radius-2 neighbourhood after the birth/survival passes alone,
+        // so they only match CaRule::classic()'s documented behavior if the
+        // isolated-pocket rule actually runs.
+        let map = Map::gen_cave_seed(10, 10, String::from("2"));
+        let map_string = format!("{}", map);
+        let expected_map_string = String::from(
+            "\
+############
+####...#####
+###.......##
+##.........#
+##.....#...#
+#...#......#
+#.........##
+#.........##
+##........##
+##........##
+###......###
+############",
+        );
+        assert_eq!(expected_map_string, map_string);
+
+        let map = Map::gen_cave_seed(10, 10, String::from("3"));
+        let map_string = format!("{}", map);
+        let expected_map_string = String::from(
+            "\
+############
+############
+###...######
+##.....#####
+##......####
+##..#....###
+##........##
+###.......##
+#######..###
+############
+############
+############",
+        );
+        assert_eq!(expected_map_string, map_string);
+    }
+
     #[test]
     fn display() {
-        let map = Map::new(5, 5);
+        let map: Map = Map::new(5, 5);
 
         let map_string = format!("{}", map);
 
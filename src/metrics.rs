@@ -0,0 +1,156 @@
+use crate::Map;
+use std::collections::VecDeque;
+
+/// Quality scores for a generated [`Map`], useful for rejecting seeds that
+/// produce caves that are too open, too fragmented, or too cramped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapMetrics {
+    /// Fraction of cells that are floor.
+    pub open_ratio: f64,
+    /// Number of disjoint floor regions, via [`Map::connected_regions`].
+    pub region_count: usize,
+    /// Size of the largest region divided by the total number of floor cells.
+    pub largest_region_ratio: f64,
+    /// Average, over all floor cells, of the distance to the nearest wall
+    /// inside the map. Cells outside the map do not count as walls here, and
+    /// a floor cell with no wall anywhere in the map contributes 0.
+    pub mean_corridor_width: f64,
+}
+
+impl<D> Map<D> {
+    /// Computes quality metrics for this map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(5, 5);
+    /// let metrics = map.metrics();
+    ///
+    /// assert_eq!(1.0, metrics.open_ratio);
+    /// assert_eq!(1, metrics.region_count);
+    /// assert_eq!(1.0, metrics.largest_region_ratio);
+    /// ```
+    pub fn metrics(&self) -> MapMetrics {
+        let total_cells = self.height * self.width;
+        let regions = self.connected_regions();
+        let floor_cells: usize = regions.iter().map(Vec::len).sum();
+
+        let open_ratio = if total_cells == 0 {
+            0.0
+        } else {
+            floor_cells as f64 / total_cells as f64
+        };
+
+        let largest_region_ratio = if floor_cells == 0 {
+            0.0
+        } else {
+            let largest = regions.iter().map(Vec::len).max().unwrap_or(0);
+            largest as f64 / floor_cells as f64
+        };
+
+        MapMetrics {
+            open_ratio,
+            region_count: regions.len(),
+            largest_region_ratio,
+            mean_corridor_width: self.mean_corridor_width(floor_cells),
+        }
+    }
+
+    fn mean_corridor_width(&self, floor_cells: usize) -> f64 {
+        if floor_cells == 0 {
+            return 0.0;
+        }
+
+        let distances = self.distance_to_nearest_wall();
+        let total: usize = distances.iter().filter_map(|d| *d).sum();
+
+        total as f64 / floor_cells as f64
+    }
+
+    /// Multi-source BFS seeded from every wall cell, giving each floor cell
+    /// its distance to the nearest wall.
+    fn distance_to_nearest_wall(&self) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.height * self.width];
+        let mut queue = VecDeque::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(y, x) {
+                    distances[y * self.width + x] = Some(0);
+                    queue.push_back((y, x));
+                }
+            }
+        }
+
+        while let Some((y, x)) = queue.pop_front() {
+            let dist = distances[y * self.width + x].expect("cell was queued, so it was visited");
+
+            for (ny, nx) in self.neighbours(y, x) {
+                let idx = ny * self.width + nx;
+                if distances[idx].is_some() {
+                    continue;
+                }
+
+                distances[idx] = Some(dist + 1);
+                queue.push_back((ny, nx));
+            }
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(y, x) {
+                    distances[y * self.width + x] = None;
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_map_is_fully_open_single_region() {
+        let map: Map = Map::new(5, 5);
+        let metrics = map.metrics();
+
+        assert_eq!(1.0, metrics.open_ratio);
+        assert_eq!(1, metrics.region_count);
+        assert_eq!(1.0, metrics.largest_region_ratio);
+    }
+
+    #[test]
+    fn fragmented_map_reports_multiple_regions() {
+        let mut map: Map = Map::new(3, 3);
+        for x in 0..3 {
+            map.set(1, x, true);
+        }
+
+        let metrics = map.metrics();
+
+        assert_eq!(2, metrics.region_count);
+        assert_eq!(0.5, metrics.largest_region_ratio);
+        assert!((6.0 / 9.0 - metrics.open_ratio).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn mean_corridor_width_is_zero_with_no_walls() {
+        let map: Map = Map::new(1, 5);
+        assert_eq!(0.0, map.metrics().mean_corridor_width);
+    }
+
+    #[test]
+    fn mean_corridor_width_averages_distance_to_nearest_wall() {
+        let mut map: Map = Map::new(1, 5);
+        map.set(0, 0, true);
+        map.set(0, 4, true);
+
+        let mean_width = map.metrics().mean_corridor_width;
+        assert!((4.0 / 3.0 - mean_width).abs() < f64::EPSILON);
+    }
+}
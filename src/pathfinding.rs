@@ -0,0 +1,174 @@
+use crate::Map;
+use std::collections::VecDeque;
+
+impl<D> Map<D> {
+    /// Runs a breadth-first search from `start` over walkable floor cells
+    /// using 4-connectivity, returning the step count to every reachable
+    /// cell (index `y * width + x`) or `None` for walls and unreachable
+    /// cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(5, 5);
+    /// let distances = map.distance_map((0, 0));
+    ///
+    /// assert_eq!(Some(0), distances[0]);
+    /// assert_eq!(Some(1), distances[1]);
+    /// ```
+    pub fn distance_map(&self, start: (usize, usize)) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.height * self.width];
+        if self.get(start.0, start.1) {
+            return distances;
+        }
+
+        distances[start.0 * self.width + start.1] = Some(0);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        while let Some((y, x)) = queue.pop_front() {
+            let dist = distances[y * self.width + x].expect("cell was queued, so it was visited");
+
+            for (ny, nx) in self.neighbours(y, x) {
+                let idx = ny * self.width + nx;
+                if self.get(ny, nx) || distances[idx].is_some() {
+                    continue;
+                }
+
+                distances[idx] = Some(dist + 1);
+                queue.push_back((ny, nx));
+            }
+        }
+
+        distances
+    }
+
+    /// Returns the reachable floor cell with the maximum distance from
+    /// `start`, or `None` if `start` cannot reach any other cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(1, 5);
+    /// assert_eq!(Some((0, 4)), map.farthest_floor((0, 0)));
+    /// ```
+    pub fn farthest_floor(&self, start: (usize, usize)) -> Option<(usize, usize)> {
+        let distances = self.distance_map(start);
+
+        distances
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, dist)| dist.filter(|&d| d > 0).map(|d| (idx, d)))
+            .max_by_key(|&(_, dist)| dist)
+            .map(|(idx, _)| (idx / self.width, idx % self.width))
+    }
+
+    /// Reconstructs a shortest path from `start` to `goal` by walking
+    /// decreasing-distance neighbours back from the goal, returning `None`
+    /// if `goal` is out of bounds or unreachable from `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use map_generator::Map;
+    ///
+    /// let map: Map = Map::new(1, 5);
+    /// let path = map.path((0, 0), (0, 4)).unwrap();
+    ///
+    /// assert_eq!(5, path.len());
+    /// assert_eq!((0, 0), path[0]);
+    /// assert_eq!((0, 4), path[4]);
+    ///
+    /// assert_eq!(None, map.path((0, 0), (5, 5)));
+    /// ```
+    pub fn path(&self, start: (usize, usize), goal: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        if goal.0 >= self.height || goal.1 >= self.width {
+            return None;
+        }
+
+        let distances = self.distance_map(start);
+        distances[goal.0 * self.width + goal.1]?;
+
+        let mut path = vec![goal];
+        let mut current = goal;
+
+        while current != start {
+            let dist = distances[current.0 * self.width + current.1]
+                .expect("every cell on the path back to start is reachable");
+
+            let next = self
+                .neighbours(current.0, current.1)
+                .into_iter()
+                .find(|&(ny, nx)| distances[ny * self.width + nx] == Some(dist - 1))
+                .expect("start is reachable, so a shorter neighbour always exists");
+
+            path.push(next);
+            current = next;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_map_from_corner() {
+        let map: Map = Map::new(3, 3);
+        let distances = map.distance_map((0, 0));
+
+        assert_eq!(Some(0), distances[0]);
+        assert_eq!(Some(1), distances[1]);
+        assert_eq!(Some(2), distances[4]);
+        assert_eq!(Some(4), distances[8]);
+    }
+
+    #[test]
+    fn distance_map_blocked_by_walls() {
+        let mut map: Map = Map::new(1, 1);
+        map.set(0, 0, true);
+
+        let distances = map.distance_map((0, 0));
+        assert_eq!(None, distances[0]);
+    }
+
+    #[test]
+    fn farthest_floor_picks_maximum_distance() {
+        let map: Map = Map::new(1, 5);
+        assert_eq!(Some((0, 4)), map.farthest_floor((0, 0)));
+    }
+
+    #[test]
+    fn path_reconstructs_shortest_route() {
+        let map: Map = Map::new(1, 5);
+        let path = map.path((0, 0), (0, 4)).unwrap();
+
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)],
+            path
+        );
+    }
+
+    #[test]
+    fn path_none_when_goal_out_of_bounds() {
+        let map: Map = Map::new(3, 3);
+
+        assert_eq!(None, map.path((0, 0), (5, 5)));
+        assert_eq!(None, map.path((0, 0), (0, 5)));
+    }
+
+    #[test]
+    fn path_none_when_unreachable() {
+        let mut map: Map = Map::new(1, 3);
+        map.set(0, 1, true);
+
+        assert_eq!(None, map.path((0, 0), (0, 2)));
+    }
+}